@@ -11,8 +11,8 @@ use vector_core::config::proxy::ProxyConfig;
 use vector_core::config::LogNamespace;
 
 use super::{
-    request_builder::ElasticsearchRequestBuilder, ElasticsearchApiVersion, ElasticsearchEncoder,
-    InvalidHostSnafu, Request,
+    request_builder::ElasticsearchRequestBuilder, BulkAction, ElasticsearchApiVersion,
+    ElasticsearchEncoder, InvalidHostSnafu, Request,
 };
 use crate::{
     http::{Auth, HttpClient, MaybeAuth},
@@ -27,6 +27,66 @@ use crate::{
     transforms::metric_to_log::MetricToLog,
 };
 
+/// Pinned `Elastic-Api-Version` header value sent on every request when
+/// `api_version = "serverless"` is configured without an explicit override.
+const DEFAULT_ELASTIC_API_VERSION: &str = "2023-10-31";
+
+/// Default `filter_path` applied to the `_bulk` request so the response only
+/// carries the fields needed to detect per-item failures. An empty string
+/// (explicitly configured) disables filtering and requests the full response.
+const DEFAULT_BULK_FILTER_PATH: &str = "took,errors,items.*.error,items.*.status";
+
+/// An Elasticsearch/OpenSearch product version, as reported by `version.number`
+/// on the root (`/`) endpoint, e.g. `"8.11.3"` or `"8.11.3-SNAPSHOT"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ElasticsearchVersion {
+    pub major: usize,
+    pub minor: usize,
+    pub patch: usize,
+}
+
+impl ElasticsearchVersion {
+    const fn new(major: usize, minor: usize, patch: usize) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parses a `version.number` value such as `"8.11.3"`, tolerating a
+    /// trailing pre-release suffix like `"8.11.3-SNAPSHOT"` or `"8.0.0-alpha1"`
+    /// by truncating at the first character that isn't a digit or a dot.
+    fn parse(number: &str) -> Option<Self> {
+        let truncated = match number.find(|c: char| !c.is_ascii_digit() && c != '.') {
+            Some(index) => &number[..index],
+            None => number,
+        };
+        let mut parts = truncated.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().unwrap_or("0").parse().ok()?;
+        let patch = parts.next().unwrap_or("0").parse().ok()?;
+        Some(Self::new(major, minor, patch))
+    }
+}
+
+/// The result of probing an endpoint's root (`/`) response: its parsed
+/// product version plus the `version.distribution` it reported, if any
+/// (e.g. `Some("opensearch")`). Public because it appears in
+/// [`ElasticsearchCommon::parse_config`]'s signature, which callers use to
+/// cache the probe result across multiple configured endpoints.
+#[derive(Debug, Clone)]
+pub struct VersionInfo {
+    pub version: ElasticsearchVersion,
+    pub distribution: Option<String>,
+}
+
+impl VersionInfo {
+    fn is_opensearch(&self) -> bool {
+        self.distribution.as_deref() == Some("opensearch")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ElasticsearchCommon {
     pub base_url: String,
@@ -40,6 +100,8 @@ pub struct ElasticsearchCommon {
     pub request: RequestConfig,
     pub query_params: HashMap<String, String>,
     pub metric_to_log: MetricToLog,
+    pub version: ElasticsearchVersion,
+    pub distribution: Option<String>,
 }
 
 impl ElasticsearchCommon {
@@ -47,7 +109,7 @@ impl ElasticsearchCommon {
         config: &ElasticsearchConfig,
         endpoint: &str,
         proxy_config: &ProxyConfig,
-        version: &mut Option<usize>,
+        version: &mut Option<VersionInfo>,
     ) -> crate::Result<Self> {
         // Test the configured host, but ignore the result
         let uri = format!("{}/_test", endpoint);
@@ -66,14 +128,20 @@ impl ElasticsearchCommon {
                 user: user.clone(),
                 password: password.clone(),
             }),
-            _ => None,
+            Some(ElasticsearchAuth::ApiKey { id, api_key }) => Some(Auth::ApiKey {
+                id: id.clone(),
+                api_key: api_key.clone(),
+            }),
+            Some(ElasticsearchAuth::Bearer { token }) => Some(Auth::Bearer {
+                token: token.clone(),
+            }),
+            Some(ElasticsearchAuth::Aws(_)) | None => None,
         };
         let uri = endpoint.parse::<UriSerde>()?;
         let http_auth = authorization.choose_one(&uri.auth)?;
         let base_url = uri.uri.to_string().trim_end_matches('/').to_owned();
 
         let aws_auth = match &config.auth {
-            Some(ElasticsearchAuth::Basic { .. }) | None => None,
             Some(ElasticsearchAuth::Aws(aws)) => {
                 let region = config
                     .aws
@@ -84,6 +152,10 @@ impl ElasticsearchCommon {
 
                 Some(aws.credentials_provider(region).await?)
             }
+            Some(ElasticsearchAuth::Basic { .. })
+            | Some(ElasticsearchAuth::ApiKey { .. })
+            | Some(ElasticsearchAuth::Bearer { .. })
+            | None => None,
         };
 
         let mode = config.common_mode()?;
@@ -103,6 +175,34 @@ impl ElasticsearchCommon {
             query_params.insert("pipeline".into(), pipeline.into());
         }
 
+        // Bulk responses echo back one entry per item, and for large batches
+        // that's dominated by entries for items that just succeeded. Restrict
+        // the response to the fields the sink actually inspects so Elasticsearch
+        // doesn't have to serialize (and Vector doesn't have to parse) the rest.
+        let filter_path = config
+            .bulk
+            .filter_path
+            .clone()
+            .unwrap_or_else(|| DEFAULT_BULK_FILTER_PATH.to_string());
+        if !filter_path.is_empty() {
+            // `partition_bulk_response` trusts position `i` in the response to
+            // mean batch item `i`; that's only true as long as every item keeps
+            // at least one field, since Elasticsearch/OpenSearch drop an item
+            // down to nothing (and out of the array) once a filter_path leaves
+            // it with no matching fields. `status` is present on every item
+            // regardless of outcome, so force it into the filter even if a
+            // user-supplied `filter_path` omitted it.
+            let filter_path = if filter_path
+                .split(',')
+                .any(|field| field == "items.*.status")
+            {
+                filter_path
+            } else {
+                format!("{filter_path},items.*.status")
+            };
+            query_params.insert("filter_path".into(), filter_path);
+        }
+
         let bulk_url = {
             let mut query = url::form_urlencoded::Serializer::new(String::new());
             for (p, v) in &query_params {
@@ -114,7 +214,22 @@ impl ElasticsearchCommon {
 
         let tls_settings = TlsSettings::from_options(&config.tls)?;
         let config = config.clone();
-        let request = config.request;
+        let mut request = config.request;
+
+        // Serverless deployments reject requests that don't pin an API
+        // version, so thread the header through
+        // both the healthcheck (`get`) and bulk (`ElasticsearchRequestBuilder`) paths
+        // via the shared `RequestConfig`.
+        let is_serverless = matches!(config.api_version, ElasticsearchApiVersion::Serverless);
+        if is_serverless {
+            request.headers.insert(
+                "Elastic-Api-Version".to_string(),
+                config
+                    .elastic_api_version
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_ELASTIC_API_VERSION.to_string()),
+            );
+        }
 
         let metric_config = config.metrics.clone().unwrap_or_default();
         let metric_to_log = MetricToLog::new(
@@ -126,15 +241,30 @@ impl ElasticsearchCommon {
 
         let region = config.aws.as_ref().and_then(|config| config.region());
 
-        let version = if let Some(version) = *version {
-            version
+        let version_info = if let Some(version_info) = version.clone() {
+            version_info
         } else {
-            let ver = match config.api_version {
-                ElasticsearchApiVersion::V6 => 6,
-                ElasticsearchApiVersion::V7 => 7,
-                ElasticsearchApiVersion::V8 => 8,
+            let info = match config.api_version {
+                ElasticsearchApiVersion::V6 => VersionInfo {
+                    version: ElasticsearchVersion::new(6, 0, 0),
+                    distribution: None,
+                },
+                ElasticsearchApiVersion::V7 => VersionInfo {
+                    version: ElasticsearchVersion::new(7, 0, 0),
+                    distribution: None,
+                },
+                ElasticsearchApiVersion::V8 => VersionInfo {
+                    version: ElasticsearchVersion::new(8, 0, 0),
+                    distribution: None,
+                },
+                // Serverless has no cluster state to probe; it's always on the
+                // latest (version 8) bulk framing.
+                ElasticsearchApiVersion::Serverless => VersionInfo {
+                    version: ElasticsearchVersion::new(8, 0, 0),
+                    distribution: None,
+                },
                 ElasticsearchApiVersion::Auto => {
-                    match get_version(
+                    match get_version_info(
                         &base_url,
                         &http_auth,
                         &aws_auth,
@@ -145,7 +275,7 @@ impl ElasticsearchCommon {
                     )
                     .await
                     {
-                        Ok(version) => version,
+                        Ok(info) => info,
                         // This error should be fatal, but for now we only emit it as a warning
                         // to make the transition smoother.
                         Err(error) => {
@@ -155,30 +285,51 @@ impl ElasticsearchCommon {
                             // Otherwise, assume the latest version (V8).
                             // This is by no means a perfect assumption but it's the best we can
                             // make with the data we have.
-                            let assumed_version = if config.suppress_type_name { 6 } else { 8 };
+                            let assumed_major = if config.suppress_type_name { 6 } else { 8 };
                             debug!(message = "Assumed ElasticsearchApi based on config setting suppress_type_name.",
-                                   %assumed_version,
+                                   %assumed_major,
                                    %config.suppress_type_name
                             );
-                            warn!(message = "Failed to determine Elasticsearch version from `/_cluster/state/version`. Please fix the reported error or set an API version explicitly via `api_version`.",
-                                  %assumed_version,
+                            warn!(message = "Failed to determine Elasticsearch version from `/`. Please fix the reported error or set an API version explicitly via `api_version`.",
+                                  %assumed_major,
                                   %error
                             );
-                            assumed_version
+                            VersionInfo {
+                                version: ElasticsearchVersion::new(assumed_major, 0, 0),
+                                distribution: None,
+                            }
                         }
                     }
                 }
             };
-            *version = Some(ver);
-            ver
+            *version = Some(info.clone());
+            info
         };
 
         let doc_type = config.doc_type.clone();
-        let suppress_type_name = if config.suppress_type_name {
+        let suppress_type_name = if is_serverless {
+            // Serverless is always on version-8 bulk framing, regardless of
+            // whatever `suppress_type_name` was (deprecatedly) set to.
+            false
+        } else if config.suppress_type_name {
             warn!(message = "DEPRECATION, use of deprecated option `suppress_type_name`. Please use `api_version` option instead.");
             config.suppress_type_name
         } else {
-            version >= 7
+            // OpenSearch forked from Elasticsearch 7.10, so every OpenSearch
+            // release speaks the version-7+ bulk dialect regardless of its own
+            // major version number.
+            version_info.is_opensearch() || version_info.version.major >= 7
+        };
+        // Data streams are append-only, so `create` (rejects on conflict)
+        // replaces `index` (upserts) wherever the endpoint supports it.
+        let bulk_action = if matches!(mode, ElasticsearchCommonMode::DataStream)
+            && supports_data_stream_create_action(
+                version_info.distribution.as_deref(),
+                version_info.version,
+            ) {
+            BulkAction::Create
+        } else {
+            BulkAction::Index
         };
         let request_builder = ElasticsearchRequestBuilder {
             compression: config.compression,
@@ -186,10 +337,11 @@ impl ElasticsearchCommon {
                 transformer: config.encoding.clone(),
                 doc_type,
                 suppress_type_name,
+                bulk_action,
             },
         };
 
-        Ok(Self {
+        let common = Self {
             http_auth,
             base_url,
             bulk_uri,
@@ -201,7 +353,15 @@ impl ElasticsearchCommon {
             region,
             tls_settings,
             metric_to_log,
-        })
+            version: version_info.version,
+            distribution: version_info.distribution,
+        };
+
+        if !common.supports_composable_index_templates() {
+            debug!(message = "Detected Elasticsearch/OpenSearch version does not support composable index templates; legacy templates must be used instead.");
+        }
+
+        Ok(common)
     }
 
     /// Parses endpoints into a vector of ElasticsearchCommons. The resulting vector is guaranteed to not be empty.
@@ -257,6 +417,32 @@ impl ElasticsearchCommon {
             status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
         }
     }
+
+    /// Composable index templates were added in Elasticsearch 7.8, and are
+    /// supported by every OpenSearch release (forked post-7.10).
+    pub fn supports_composable_index_templates(&self) -> bool {
+        supports_composable_index_templates(self.distribution.as_deref(), self.version)
+    }
+
+    /// Data streams require the bulk `create` action instead of `index`; this
+    /// landed in Elasticsearch 7.9, and is supported by every OpenSearch release.
+    pub fn supports_data_stream_create_action(&self) -> bool {
+        supports_data_stream_create_action(self.distribution.as_deref(), self.version)
+    }
+}
+
+fn supports_composable_index_templates(
+    distribution: Option<&str>,
+    version: ElasticsearchVersion,
+) -> bool {
+    distribution == Some("opensearch") || (version.major, version.minor) >= (7, 8)
+}
+
+fn supports_data_stream_create_action(
+    distribution: Option<&str>,
+    version: ElasticsearchVersion,
+) -> bool {
+    distribution == Some("opensearch") || (version.major, version.minor) >= (7, 9)
 }
 
 pub async fn sign_request(
@@ -267,7 +453,7 @@ pub async fn sign_request(
     crate::aws::sign_request("es", request, credentials_provider, region).await
 }
 
-async fn get_version(
+async fn get_version_info(
     base_url: &str,
     http_auth: &Option<Auth>,
     aws_auth: &Option<SharedCredentialsProvider>,
@@ -275,30 +461,43 @@ async fn get_version(
     request: &RequestConfig,
     tls_settings: &TlsSettings,
     proxy_config: &ProxyConfig,
-) -> crate::Result<usize> {
+) -> crate::Result<VersionInfo> {
     #[derive(Deserialize)]
-    struct ClusterState {
-        version: Option<usize>,
+    struct RootResponse {
+        version: VersionField,
+    }
+
+    #[derive(Deserialize)]
+    struct VersionField {
+        number: String,
+        distribution: Option<String>,
     }
 
     let client = HttpClient::new(tls_settings.clone(), proxy_config)?;
-    let response = get(
-        base_url,
-        http_auth,
-        aws_auth,
-        region,
-        request,
-        client,
-        "/_cluster/state/version",
-    )
-    .await
-    .map_err(|error| format!("Failed to get Elasticsearch API version: {}", error))?;
+    let response = get(base_url, http_auth, aws_auth, region, request, client, "/")
+        .await
+        .map_err(|error| format!("Failed to get Elasticsearch version: {}", error))?;
 
     let (_, body) = response.into_parts();
     let mut body = body::aggregate(body).await?;
     let body = body.copy_to_bytes(body.remaining());
-    let ClusterState { version } = serde_json::from_slice(&body)?;
-    version.ok_or_else(||"Unexpected response from Elasticsearch endpoint `/_cluster/state/version`. Missing `version`. Consider setting `api_version` option.".into())
+    let RootResponse { version } = serde_json::from_slice(&body)?;
+
+    let parsed_version = ElasticsearchVersion::parse(&version.number).ok_or_else(|| {
+        format!(
+            "Unexpected response from Elasticsearch endpoint `/`. Could not parse `version.number` {:?}.",
+            version.number
+        )
+    })?;
+
+    Ok(VersionInfo {
+        version: parsed_version,
+        // `version.distribution` is the only field that identifies a fork
+        // (e.g. `"opensearch"`); `version.build_flavor` (`"default"`/`"oss"`)
+        // describes the Elasticsearch build itself and never names OpenSearch,
+        // so it isn't a usable fallback for distribution detection.
+        distribution: version.distribution,
+    })
 }
 
 async fn get(
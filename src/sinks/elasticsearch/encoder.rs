@@ -0,0 +1,26 @@
+/// Which bulk action wraps each document: `index` upserts by `_id`, `create`
+/// rejects (rather than overwrites) a document that already exists — the
+/// action data streams require, since they're append-only.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BulkAction {
+    Index,
+    Create,
+}
+
+impl BulkAction {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            BulkAction::Index => "index",
+            BulkAction::Create => "create",
+        }
+    }
+}
+
+/// Encodes events into the NDJSON lines a `_bulk` request is made of.
+#[derive(Clone, Debug)]
+pub struct ElasticsearchEncoder {
+    pub transformer: crate::codecs::Transformer,
+    pub doc_type: Option<String>,
+    pub suppress_type_name: bool,
+    pub bulk_action: BulkAction,
+}
@@ -0,0 +1,161 @@
+mod common;
+mod encoder;
+mod request_builder;
+mod response;
+#[cfg(test)]
+mod tests;
+
+use std::collections::HashMap;
+
+use snafu::Snafu;
+
+pub use common::{ElasticsearchCommon, ElasticsearchVersion, VersionInfo};
+pub use encoder::{BulkAction, ElasticsearchEncoder};
+pub use request_builder::ElasticsearchRequestBuilder;
+pub use response::{partition_bulk_response, BulkItemOutcome};
+
+use crate::{
+    aws::{AwsAuthentication, RegionOrEndpoint},
+    http::SensitiveString,
+    sinks::util::{http::RequestConfig, Compression},
+    tls::TlsOptions,
+    transforms::metric_to_log::MetricToLogConfig,
+};
+
+/// The HTTP request type used throughout the Elasticsearch sink.
+pub(crate) type Request = http::Request<bytes::Bytes>;
+
+#[derive(Debug, Snafu)]
+pub enum ParseError {
+    #[snafu(display("Invalid host {:?}: {}", host, source))]
+    InvalidHost {
+        host: String,
+        source: http::uri::InvalidUri,
+    },
+    #[snafu(display("Host {:?} must include a hostname", host))]
+    HostMustIncludeHostname { host: String },
+    #[snafu(display("Only one of `endpoint` or `endpoints` can be set"))]
+    EndpointsExclusive,
+    #[snafu(display("At least one of `endpoint` or `endpoints` must be set"))]
+    EndpointRequired,
+    #[snafu(display("AWS authentication requires a `region`"))]
+    RegionRequired,
+}
+
+/// Which bulk/indexing API dialect to speak. `Auto` probes the endpoint's
+/// root (`/`) response; every other variant is a fixed assumption, useful
+/// when the probe isn't available (e.g. `Serverless`) or reliable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElasticsearchApiVersion {
+    V6,
+    V7,
+    V8,
+    /// Elastic Cloud Serverless: skips the version probe (it has no cluster
+    /// state to query), pins bulk framing to the version-8 dialect, and
+    /// stamps every request with a configurable `Elastic-Api-Version` header.
+    Serverless,
+    #[default]
+    Auto,
+}
+
+/// How to authenticate with the configured endpoint(s).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum ElasticsearchAuth {
+    Basic {
+        user: String,
+        password: SensitiveString,
+    },
+    Aws(AwsAuthentication),
+    /// Elasticsearch/Elastic Cloud API-key authentication: sent as
+    /// `Authorization: ApiKey <base64(id:api_key)>`.
+    ApiKey {
+        id: SensitiveString,
+        api_key: SensitiveString,
+    },
+    /// A pre-issued bearer/access token: sent as `Authorization: Bearer <token>`.
+    Bearer { token: SensitiveString },
+}
+
+/// Settings for the `_bulk` request itself.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct BulkConfig {
+    pub index: Option<String>,
+    pub action: Option<String>,
+    /// Restricts the `_bulk` response to the listed fields (via
+    /// Elasticsearch/OpenSearch's `filter_path` query parameter) so large
+    /// batches dominated by successful items don't have to be serialized or
+    /// parsed in full. Set to an empty string to request the unfiltered
+    /// response. `items.*.status` is always kept regardless of what's
+    /// configured here — dropping it would let Elasticsearch omit successful
+    /// items from the response entirely, which breaks mapping a response
+    /// item back to the batch item it belongs to.
+    pub filter_path: Option<String>,
+}
+
+/// Whether to index documents individually (`Bulk`) or write them to a
+/// data stream (`DataStream`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElasticsearchMode {
+    #[default]
+    Bulk,
+    DataStream,
+}
+
+#[derive(Clone, Debug)]
+pub enum ElasticsearchCommonMode {
+    Bulk {
+        index: String,
+        action: Option<String>,
+    },
+    DataStream,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct ElasticsearchConfig {
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub endpoints: Vec<String>,
+    pub doc_type: Option<String>,
+    #[serde(default)]
+    pub suppress_type_name: bool,
+    #[serde(default)]
+    pub api_version: ElasticsearchApiVersion,
+    /// Overrides the `Elastic-Api-Version` header value sent when
+    /// `api_version = "serverless"`. Defaults to a pinned date if unset.
+    pub elastic_api_version: Option<String>,
+    pub auth: Option<ElasticsearchAuth>,
+    pub aws: Option<RegionOrEndpoint>,
+    pub query: Option<HashMap<String, String>>,
+    pub pipeline: Option<String>,
+    #[serde(default)]
+    pub mode: ElasticsearchMode,
+    #[serde(default)]
+    pub bulk: BulkConfig,
+    #[serde(default)]
+    pub compression: Compression,
+    #[serde(default)]
+    pub encoding: crate::codecs::Transformer,
+    pub tls: Option<TlsOptions>,
+    #[serde(default)]
+    pub request: RequestConfig,
+    pub metrics: Option<MetricToLogConfig>,
+}
+
+impl ElasticsearchConfig {
+    pub fn common_mode(&self) -> crate::Result<ElasticsearchCommonMode> {
+        Ok(match self.mode {
+            ElasticsearchMode::Bulk => ElasticsearchCommonMode::Bulk {
+                index: self
+                    .bulk
+                    .index
+                    .clone()
+                    .unwrap_or_else(|| "vector".to_string()),
+                action: self.bulk.action.clone(),
+            },
+            ElasticsearchMode::DataStream => ElasticsearchCommonMode::DataStream,
+        })
+    }
+}
@@ -0,0 +1,10 @@
+use crate::sinks::util::Compression;
+
+use super::encoder::ElasticsearchEncoder;
+
+/// Builds the encoded, compressed bodies that make up a `_bulk` request.
+#[derive(Clone, Debug)]
+pub struct ElasticsearchRequestBuilder {
+    pub compression: Compression,
+    pub encoder: ElasticsearchEncoder,
+}
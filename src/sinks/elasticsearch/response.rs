@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// The outcome Vector derives for a single event in a bulk request, based on
+/// the (possibly `filter_path`-trimmed) response entry Elasticsearch/OpenSearch
+/// returned for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BulkItemOutcome {
+    Success,
+    /// Transient failure (e.g. `429`, `5xx`); the event should be retried.
+    Retriable,
+    /// The document itself was rejected; retrying it unchanged won't help.
+    Permanent,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BulkResponse {
+    #[serde(default)]
+    items: Vec<HashMap<String, BulkResponseItem>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BulkResponseItem {
+    status: Option<u16>,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+/// Parses a `_bulk` response body and returns one [`BulkItemOutcome`] per item
+/// in the original batch, in the original batch order.
+///
+/// Elasticsearch/OpenSearch return one `items` array entry per submitted
+/// action, in order, *as long as every item keeps at least one field* —
+/// `ElasticsearchCommon` enforces this by always keeping `items.*.status` in
+/// `filter_path`, so a successful item comes back as `{"status": 200}` rather
+/// than being pruned away entirely. Position `i` in `items` is only safe to
+/// read as batch item `i` under that guarantee.
+///
+/// If `items` doesn't have exactly `batch_len` entries, that guarantee has
+/// been violated (e.g. a caller-supplied `filter_path` dropped `status`, or
+/// an intermediary truncated the response) and position no longer reliably
+/// identifies a batch item — a failure could have shifted earlier in the
+/// array by however many successes were dropped ahead of it. Rather than
+/// risk silently acking a event that actually failed, treat the whole batch
+/// as indeterminate and retry it.
+pub fn partition_bulk_response(
+    body: &[u8],
+    batch_len: usize,
+) -> crate::Result<Vec<BulkItemOutcome>> {
+    let BulkResponse { items } = serde_json::from_slice(body)?;
+
+    if items.len() != batch_len {
+        return Ok(vec![BulkItemOutcome::Retriable; batch_len]);
+    }
+
+    Ok(items
+        .into_iter()
+        .map(|item| match item.into_values().next() {
+            Some(item) => classify(&item),
+            None => BulkItemOutcome::Success,
+        })
+        .collect())
+}
+
+fn classify(item: &BulkResponseItem) -> BulkItemOutcome {
+    if item.error.is_none() {
+        return BulkItemOutcome::Success;
+    }
+    match item.status {
+        Some(status) if status == 429 || status >= 500 => BulkItemOutcome::Retriable,
+        Some(_) => BulkItemOutcome::Permanent,
+        // `ElasticsearchCommon` always keeps `items.*.status` in the request's
+        // `filter_path`, so this only fires for a hand-rolled response (e.g. in
+        // tests) that reports an error without one. Guess the outcome that's
+        // safe to retry rather than assume the document was rejected for good.
+        None => BulkItemOutcome::Retriable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unfiltered_response_maps_each_item_by_position() {
+        let body = br#"{
+            "items": [
+                {"index": {"status": 201}},
+                {"index": {"status": 400, "error": {"type": "mapper_parsing_exception"}}},
+                {"index": {"status": 201}}
+            ]
+        }"#;
+        let outcomes = partition_bulk_response(body, 3).unwrap();
+        assert_eq!(
+            outcomes,
+            vec![
+                BulkItemOutcome::Success,
+                BulkItemOutcome::Permanent,
+                BulkItemOutcome::Success
+            ]
+        );
+    }
+
+    #[test]
+    fn filtered_response_keeps_status_on_success_items() {
+        // `filter_path=items.*.status,items.*.error` keeps every item present
+        // (forced by `ElasticsearchCommon`); successes are pruned down to
+        // `{"status": N}` rather than being dropped from the array.
+        let body = br#"{
+            "items": [
+                {"index": {"status": 201}},
+                {"index": {"status": 429, "error": {"type": "es_rejected_execution_exception"}}},
+                {"index": {"status": 201}}
+            ]
+        }"#;
+        let outcomes = partition_bulk_response(body, 3).unwrap();
+        assert_eq!(
+            outcomes,
+            vec![
+                BulkItemOutcome::Success,
+                BulkItemOutcome::Retriable,
+                BulkItemOutcome::Success
+            ]
+        );
+    }
+
+    #[test]
+    fn error_without_a_status_defaults_to_retriable() {
+        let body = br#"{"items": [{"index": {"error": {"type": "unknown"}}}]}"#;
+        let outcomes = partition_bulk_response(body, 1).unwrap();
+        assert_eq!(outcomes, vec![BulkItemOutcome::Retriable]);
+    }
+
+    #[test]
+    fn shorter_response_retries_the_whole_batch() {
+        // A misconfigured `filter_path` dropped `items.*.status`, so
+        // Elasticsearch omitted the two successful items entirely, leaving
+        // only the failure from batch position 2 as the sole array element.
+        // Positionally this would misattribute the failure to position 0 and
+        // default positions 1/2 to `Success`; instead the whole batch must be
+        // retried since position can no longer be trusted.
+        let body = br#"{
+            "items": [
+                {"index": {"status": 400, "error": {"type": "mapper_parsing_exception"}}}
+            ]
+        }"#;
+        let outcomes = partition_bulk_response(body, 3).unwrap();
+        assert_eq!(
+            outcomes,
+            vec![
+                BulkItemOutcome::Retriable,
+                BulkItemOutcome::Retriable,
+                BulkItemOutcome::Retriable
+            ]
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use super::*;
+
+fn test_config() -> ElasticsearchConfig {
+    ElasticsearchConfig {
+        endpoints: vec!["http://localhost:9200".to_string()],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn serverless_injects_default_elastic_api_version_header() {
+    let config = ElasticsearchConfig {
+        api_version: ElasticsearchApiVersion::Serverless,
+        ..test_config()
+    };
+
+    let common = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    assert_eq!(
+        common
+            .request
+            .headers
+            .get("Elastic-Api-Version")
+            .map(String::as_str),
+        Some("2023-10-31")
+    );
+    // Serverless always speaks the version-8 bulk dialect.
+    assert!(!common.request_builder.encoder.suppress_type_name);
+}
+
+#[tokio::test]
+async fn serverless_header_is_overridable() {
+    let config = ElasticsearchConfig {
+        api_version: ElasticsearchApiVersion::Serverless,
+        elastic_api_version: Some("2024-01-01".to_string()),
+        ..test_config()
+    };
+
+    let common = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    assert_eq!(
+        common
+            .request
+            .headers
+            .get("Elastic-Api-Version")
+            .map(String::as_str),
+        Some("2024-01-01")
+    );
+}
+
+#[tokio::test]
+async fn non_serverless_config_has_no_elastic_api_version_header() {
+    let config = ElasticsearchConfig {
+        api_version: ElasticsearchApiVersion::V8,
+        ..test_config()
+    };
+
+    let common = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    assert!(!common.request.headers.contains_key("Elastic-Api-Version"));
+}
+
+#[tokio::test]
+async fn filter_path_defaults_into_bulk_query_params() {
+    let common = ElasticsearchCommon::parse_single(&test_config())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        common.query_params.get("filter_path").map(String::as_str),
+        Some("took,errors,items.*.error,items.*.status")
+    );
+}
+
+#[tokio::test]
+async fn empty_filter_path_disables_filtering() {
+    let config = ElasticsearchConfig {
+        bulk: BulkConfig {
+            filter_path: Some(String::new()),
+            ..Default::default()
+        },
+        ..test_config()
+    };
+
+    let common = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    assert!(!common.query_params.contains_key("filter_path"));
+}
+
+#[tokio::test]
+async fn data_stream_uses_create_action_on_es7_9_and_newer() {
+    let config = ElasticsearchConfig {
+        api_version: ElasticsearchApiVersion::V8,
+        mode: ElasticsearchMode::DataStream,
+        ..test_config()
+    };
+
+    let common = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    assert_eq!(
+        common.request_builder.encoder.bulk_action,
+        BulkAction::Create
+    );
+}
+
+#[tokio::test]
+async fn data_stream_falls_back_to_index_action_on_old_versions() {
+    let config = ElasticsearchConfig {
+        api_version: ElasticsearchApiVersion::V6,
+        mode: ElasticsearchMode::DataStream,
+        ..test_config()
+    };
+
+    let common = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    assert_eq!(
+        common.request_builder.encoder.bulk_action,
+        BulkAction::Index
+    );
+}
+
+#[tokio::test]
+async fn bulk_mode_never_uses_create_action() {
+    let config = ElasticsearchConfig {
+        api_version: ElasticsearchApiVersion::V8,
+        mode: ElasticsearchMode::Bulk,
+        ..test_config()
+    };
+
+    let common = ElasticsearchCommon::parse_single(&config).await.unwrap();
+
+    assert_eq!(
+        common.request_builder.encoder.bulk_action,
+        BulkAction::Index
+    );
+}